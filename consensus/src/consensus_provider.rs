@@ -2,6 +2,7 @@
 // Parts of the project are originally copyright © Meta Platforms, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
+use crate::pipeline::execution_client::DummyExecutionClient;
 use crate::{
     consensus_observer::publisher::consensus_publisher::ConsensusPublisher,
     counters,
@@ -25,7 +26,6 @@ use aptos_validator_transaction_pool::VTxnPoolState;
 use futures::channel::mpsc;
 use std::sync::Arc;
 use tokio::runtime::Runtime;
-use crate::pipeline::execution_client::DummyExecutionClient;
 
 /// Helper function to start consensus based on configuration and return the runtime
 #[allow(clippy::unwrap_used)]
@@ -82,7 +82,7 @@ pub fn start_consensus(
     //     consensus_publisher.clone(),
     // ));
 
-    let execution_client = Arc::new(DummyExecutionClient);
+    let execution_client = Arc::new(DummyExecutionClient::new());
 
     let epoch_mgr = EpochManager::new(
         node_config,
@@ -0,0 +1,217 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::liveness::proposal_status_tracker::{RoundStatus, TProposalStatusTracker};
+use aptos_consensus_types::common::{Author, Round};
+use std::{collections::HashSet, sync::Arc};
+
+/// A committed block's contribution to leader-reputation history: who proposed it and who
+/// voted for it.
+#[derive(Clone, Debug)]
+pub struct NewBlockEvent {
+    pub round: Round,
+    pub proposer: Author,
+    pub voters: Vec<Author>,
+}
+
+/// Produces per-candidate weights from recent on-chain history, consumed by the weighted
+/// leader-selection draw. Weights are returned in the same order as `candidates`.
+pub trait ReputationHeuristic: Send + Sync {
+    fn get_weights(&self, candidates: &[Author], history: &[NewBlockEvent]) -> Vec<u64>;
+}
+
+fn proposed_in(author: &Author, history: &[NewBlockEvent]) -> bool {
+    history.iter().any(|event| &event.proposer == author)
+}
+
+fn voted_in(author: &Author, history: &[NewBlockEvent]) -> bool {
+    history.iter().any(|event| event.voters.contains(author))
+}
+
+/// Rewards validators that both proposed and voted within the window, and gives a lower
+/// weight to validators that only voted.
+pub struct ProposerAndVoterHeuristic {
+    active_weight: u64,
+    inactive_weight: u64,
+}
+
+impl ProposerAndVoterHeuristic {
+    pub fn new(active_weight: u64, inactive_weight: u64) -> Self {
+        Self {
+            active_weight,
+            inactive_weight,
+        }
+    }
+}
+
+impl ReputationHeuristic for ProposerAndVoterHeuristic {
+    fn get_weights(&self, candidates: &[Author], history: &[NewBlockEvent]) -> Vec<u64> {
+        candidates
+            .iter()
+            .map(|candidate| {
+                if proposed_in(candidate, history) && voted_in(candidate, history) {
+                    self.active_weight
+                } else {
+                    self.inactive_weight
+                }
+            })
+            .collect()
+    }
+}
+
+/// Like `ProposerAndVoterHeuristic`, but additionally penalizes validators whose assigned
+/// rounds ended in a timeout/NIL block rather than a committed proposal, per
+/// `TProposalStatusTracker`. A validator that both proposed and voted gets `base_active_weight`;
+/// one that only voted gets `inactive_weight`; one that was the designated leader of a
+/// timed-out round gets `failed_weight`, the lowest of the three. The failed-leader set is read
+/// from the local `TProposalStatusTracker`, so two validators derive the same weights only when
+/// their trackers hold the same round statuses; nothing here reconciles trackers across
+/// validators.
+pub struct ReputationHeuristicWithTimeout {
+    base_active_weight: u64,
+    inactive_weight: u64,
+    failed_weight: u64,
+    proposal_status_tracker: Arc<dyn TProposalStatusTracker>,
+}
+
+impl ReputationHeuristicWithTimeout {
+    pub fn new(
+        base_active_weight: u64,
+        inactive_weight: u64,
+        failed_weight: u64,
+        proposal_status_tracker: Arc<dyn TProposalStatusTracker>,
+    ) -> Self {
+        Self {
+            base_active_weight,
+            inactive_weight,
+            failed_weight,
+            proposal_status_tracker,
+        }
+    }
+
+    /// Validators that were the expected leader of a round the tracker recorded as timed out,
+    /// within the tracked window.
+    fn failed_leaders(&self) -> HashSet<Author> {
+        self.proposal_status_tracker
+            .round_statuses()
+            .into_iter()
+            .filter_map(|(_round, author, status)| {
+                (status == RoundStatus::TimedOut).then_some(author)
+            })
+            .collect()
+    }
+}
+
+impl ReputationHeuristic for ReputationHeuristicWithTimeout {
+    fn get_weights(&self, candidates: &[Author], history: &[NewBlockEvent]) -> Vec<u64> {
+        let failed_leaders = self.failed_leaders();
+        candidates
+            .iter()
+            .map(|candidate| {
+                // Failed-leader status is checked ahead of "proposed and voted in the window": a
+                // validator that timed out as leader at least once gets `failed_weight` even if
+                // it also proposed and voted elsewhere.
+                if failed_leaders.contains(candidate) {
+                    self.failed_weight
+                } else if proposed_in(candidate, history) && voted_in(candidate, history) {
+                    self.base_active_weight
+                } else {
+                    self.inactive_weight
+                }
+            })
+            .collect()
+    }
+}
+
+/// Builds a `ReputationHeuristicWithTimeout` from the three weights passed in by the caller;
+/// this function does not read them from any on-chain config itself.
+pub fn create_reputation_heuristic(
+    base_active_weight: u64,
+    inactive_weight: u64,
+    failed_weight: u64,
+    proposal_status_tracker: Arc<dyn TProposalStatusTracker>,
+) -> Arc<dyn ReputationHeuristic> {
+    Arc::new(ReputationHeuristicWithTimeout::new(
+        base_active_weight,
+        inactive_weight,
+        failed_weight,
+        proposal_status_tracker,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::liveness::proposal_status_tracker::ProposalStatusTracker;
+
+    const BASE_ACTIVE_WEIGHT: u64 = 100;
+    const INACTIVE_WEIGHT: u64 = 10;
+    const FAILED_WEIGHT: u64 = 1;
+
+    fn heuristic(tracker: ProposalStatusTracker) -> ReputationHeuristicWithTimeout {
+        ReputationHeuristicWithTimeout::new(
+            BASE_ACTIVE_WEIGHT,
+            INACTIVE_WEIGHT,
+            FAILED_WEIGHT,
+            Arc::new(tracker),
+        )
+    }
+
+    #[test]
+    fn a_failed_leader_that_also_voted_still_gets_failed_weight() {
+        let culprit = Author::random();
+        let other = Author::random();
+
+        let tracker = ProposalStatusTracker::new(10);
+        // `culprit` was the expected leader of round 1, and that round timed out.
+        tracker.push(1, culprit, RoundStatus::TimedOut);
+
+        // But `culprit` also voted for a block proposed by `other` in a later round, which on
+        // its own would earn it `inactive_weight` rather than the lower `failed_weight`.
+        let history = vec![NewBlockEvent {
+            round: 2,
+            proposer: other,
+            voters: vec![culprit, other],
+        }];
+
+        let weights = heuristic(tracker).get_weights(&[culprit, other], &history);
+        assert_eq!(weights, vec![FAILED_WEIGHT, BASE_ACTIVE_WEIGHT]);
+    }
+
+    #[test]
+    fn an_active_validator_with_no_timeouts_gets_base_active_weight() {
+        let validator = Author::random();
+        let tracker = ProposalStatusTracker::new(10);
+        let history = vec![NewBlockEvent {
+            round: 1,
+            proposer: validator,
+            voters: vec![validator],
+        }];
+
+        let weights = heuristic(tracker).get_weights(&[validator], &history);
+        assert_eq!(weights, vec![BASE_ACTIVE_WEIGHT]);
+    }
+
+    #[test]
+    fn a_validator_absent_from_history_and_failed_leaders_gets_inactive_weight() {
+        let validator = Author::random();
+        let tracker = ProposalStatusTracker::new(10);
+
+        let weights = heuristic(tracker).get_weights(&[validator], &[]);
+        assert_eq!(weights, vec![INACTIVE_WEIGHT]);
+    }
+
+    #[test]
+    fn failed_leaders_only_includes_timed_out_rounds() {
+        let timed_out_leader = Author::random();
+        let committed_leader = Author::random();
+
+        let tracker = ProposalStatusTracker::new(10);
+        tracker.push(1, timed_out_leader, RoundStatus::TimedOut);
+        tracker.push(2, committed_leader, RoundStatus::Committed);
+
+        let failed_leaders = heuristic(tracker).failed_leaders();
+        assert!(failed_leaders.contains(&timed_out_leader));
+        assert!(!failed_leaders.contains(&committed_leader));
+    }
+}
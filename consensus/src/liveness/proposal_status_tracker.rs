@@ -0,0 +1,57 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use aptos_consensus_types::common::{Author, Round};
+use std::{collections::VecDeque, sync::Mutex};
+
+/// Outcome of a round: either a proposal was committed, or the round timed out (e.g. the
+/// leader failed to propose, or the proposal failed to gather a quorum of votes in time).
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum RoundStatus {
+    Committed,
+    TimedOut,
+}
+
+/// Tracks the outcome of recently completed rounds, attributing each to the validator that was
+/// expected to lead it. `leader_reputation` consumes this to penalize validators that are
+/// chronically slow or offline.
+pub trait TProposalStatusTracker: Send + Sync {
+    /// Returns `(round, expected_leader, status)` for every tracked round, oldest first.
+    fn round_statuses(&self) -> Vec<(Round, Author, RoundStatus)>;
+}
+
+/// In-memory, bounded-window `TProposalStatusTracker`. The caller records each concluded round
+/// with `push`; the tracker does not observe rounds on its own.
+pub struct ProposalStatusTracker {
+    window_size: usize,
+    statuses: Mutex<VecDeque<(Round, Author, RoundStatus)>>,
+}
+
+impl ProposalStatusTracker {
+    pub fn new(window_size: usize) -> Self {
+        Self {
+            window_size,
+            statuses: Mutex::new(VecDeque::with_capacity(window_size)),
+        }
+    }
+
+    /// Records that `round`, whose expected leader was `author`, ended with `status`.
+    pub fn push(&self, round: Round, author: Author, status: RoundStatus) {
+        let mut statuses = self.statuses.lock().expect("lock not poisoned");
+        statuses.push_back((round, author, status));
+        if statuses.len() > self.window_size {
+            statuses.pop_front();
+        }
+    }
+}
+
+impl TProposalStatusTracker for ProposalStatusTracker {
+    fn round_statuses(&self) -> Vec<(Round, Author, RoundStatus)> {
+        self.statuses
+            .lock()
+            .expect("lock not poisoned")
+            .iter()
+            .copied()
+            .collect()
+    }
+}
@@ -9,15 +9,46 @@ use aptos_consensus_types::{
     common::Payload, payload_pull_params::PayloadPullParameters, utils::PayloadTxnsSize,
 };
 use aptos_logger::debug;
-use aptos_types::{on_chain_config::ValidatorTxnConfig, validator_txn::ValidatorTransaction};
+use aptos_types::{
+    on_chain_config::ValidatorTxnConfig, transaction::SignedTransaction,
+    validator_txn::ValidatorTransaction,
+};
 use aptos_validator_transaction_pool::TransactionFilter;
 use fail::fail_point;
-use std::{cmp::min, sync::Arc, time::Instant};
+use move_core_types::account_address::AccountAddress;
+use std::{
+    cmp::min,
+    collections::{BinaryHeap, HashMap, VecDeque},
+    sync::Arc,
+    time::Instant,
+};
+
+/// Controls how the user-txn portion of a pulled payload is ordered.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum UserTxnPullMode {
+    /// Preserve whatever order the `UserPayloadClient` returns (previous behavior).
+    Fifo,
+    /// Greedily select user txns to maximize total gas-price-weighted fees under the
+    /// `max_txns`/byte budget, without reordering any sender's txns out of nonce sequence.
+    ///
+    /// Only takes effect for `Payload::DirectMempool` payloads, since that is the only variant
+    /// that carries individual `SignedTransaction`s on the payload-client side; quorum-store-
+    /// backed variants (`InQuorumStore`, etc.) carry proof-of-store references whose underlying
+    /// txns aren't available here; those pass through unreordered and are logged when this mode
+    /// is on.
+    FeePrioritized,
+}
+
+/// When `FeePrioritized`, the candidate set requested from the `UserPayloadClient` is inflated
+/// by this factor so the fee-based selection below has more than the bare minimum to choose
+/// from.
+const FEE_PRIORITIZED_CANDIDATE_MULTIPLIER: u64 = 2;
 
 pub struct MixedPayloadClient {
     validator_txn_config: ValidatorTxnConfig,
     validator_txn_pool_client: Arc<dyn crate::payload_client::validator::ValidatorTxnPayloadClient>,
     user_payload_client: Arc<dyn UserPayloadClient>,
+    user_txn_pull_mode: UserTxnPullMode,
 }
 
 impl MixedPayloadClient {
@@ -27,11 +58,13 @@ impl MixedPayloadClient {
             dyn crate::payload_client::validator::ValidatorTxnPayloadClient,
         >,
         user_payload_client: Arc<dyn UserPayloadClient>,
+        user_txn_pull_mode: UserTxnPullMode,
     ) -> Self {
         Self {
             validator_txn_config,
             validator_txn_pool_client,
             user_payload_client,
+            user_txn_pull_mode,
         }
     }
 
@@ -51,6 +84,68 @@ impl MixedPayloadClient {
         });
         vec![]
     }
+
+    /// Greedily selects, from `txns`, the subset that maximizes total gas-price-weighted fees
+    /// under `budget`. Txns are bucketed by sender, preserving each sender's incoming
+    /// (sequence-number) order, then repeatedly picks the highest gas-priced head-of-queue txn
+    /// across senders so no account's txns are ever reordered out of nonce sequence. The count
+    /// budget is monotonic (every selection consumes exactly one slot), so selection stops
+    /// entirely once it's exhausted. The byte budget isn't: a high-priced candidate that's too
+    /// large to fit only rules out its own sender (taking it would reorder that sender's txns),
+    /// not the remaining, possibly smaller, candidates from other senders, so those are still
+    /// considered.
+    fn select_by_fee(
+        txns: Vec<SignedTransaction>,
+        budget: PayloadTxnsSize,
+    ) -> Vec<SignedTransaction> {
+        let mut by_sender: HashMap<AccountAddress, VecDeque<SignedTransaction>> = HashMap::new();
+        let mut senders: Vec<AccountAddress> = Vec::new();
+        for txn in txns {
+            let sender = txn.sender();
+            by_sender
+                .entry(sender)
+                .or_insert_with(|| {
+                    senders.push(sender);
+                    VecDeque::new()
+                })
+                .push_back(txn);
+        }
+
+        let mut heap: BinaryHeap<(u64, usize)> = BinaryHeap::new();
+        for (idx, sender) in senders.iter().enumerate() {
+            if let Some(head) = by_sender.get(sender).and_then(|q| q.front()) {
+                heap.push((head.gas_unit_price(), idx));
+            }
+        }
+
+        let mut selected = Vec::new();
+        let mut count = 0u64;
+        let mut bytes = 0u64;
+        while let Some((_, idx)) = heap.pop() {
+            if count + 1 > budget.count() {
+                break;
+            }
+            let sender = senders[idx];
+            let queue = by_sender.get_mut(&sender).expect("sender queue must exist");
+            let txn = queue.front().expect("heap entry implies a head txn");
+            let txn_bytes = txn.raw_txn_bytes_len() as u64;
+            if bytes + txn_bytes > budget.size_in_bytes() {
+                // This sender's head txn doesn't fit; it was already popped off the heap above,
+                // so it (and the rest of this sender's queue, to preserve nonce order) is simply
+                // excluded from the result. Other senders' smaller candidates are still in the
+                // heap and get a chance below.
+                continue;
+            }
+            let txn = queue.pop_front().expect("head txn was just peeked");
+            count += 1;
+            bytes += txn_bytes;
+            selected.push(txn);
+            if let Some(next) = queue.front() {
+                heap.push((next.gas_unit_price(), idx));
+            }
+        }
+        selected
+    }
 }
 
 #[async_trait::async_trait]
@@ -97,9 +192,141 @@ impl PayloadClient for MixedPayloadClient {
             .max_poll_time
             .saturating_sub(validator_txn_pull_timer.elapsed());
 
-        // Pull user payload.
+        // Pull user payload. In `FeePrioritized` mode, request a larger candidate superset than
+        // the actual budget so the fee-based selection below has more to choose from, then trim
+        // back down to the original budget.
+        let user_txn_budget = user_txn_pull_params.max_txns;
+        if self.user_txn_pull_mode == UserTxnPullMode::FeePrioritized {
+            user_txn_pull_params.max_txns = PayloadTxnsSize::new(
+                user_txn_pull_params.max_txns.count() * FEE_PRIORITIZED_CANDIDATE_MULTIPLIER,
+                user_txn_pull_params.max_txns.size_in_bytes()
+                    * FEE_PRIORITIZED_CANDIDATE_MULTIPLIER,
+            );
+        }
         let user_payload = self.user_payload_client.pull(user_txn_pull_params).await?;
+        let user_payload = match (self.user_txn_pull_mode, user_payload) {
+            (UserTxnPullMode::FeePrioritized, Payload::DirectMempool(txns)) => {
+                Payload::DirectMempool(Self::select_by_fee(txns, user_txn_budget))
+            }
+            (UserTxnPullMode::FeePrioritized, user_payload) => {
+                debug!(
+                    "FeePrioritized user_txn_pull_mode has no effect on non-DirectMempool payloads; passing through unreordered"
+                );
+                user_payload
+            }
+            (UserTxnPullMode::Fifo, user_payload) => user_payload,
+        };
 
         Ok((validator_txns, user_payload))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aptos_crypto::{ed25519::Ed25519PrivateKey, PrivateKey, Uniform};
+    use aptos_types::{
+        chain_id::ChainId,
+        transaction::{RawTransaction, Script, TransactionPayload},
+    };
+
+    /// `code_len` pads the script's code bytes so tests can control `raw_txn_bytes_len()`
+    /// independently of `gas_unit_price`/`sequence_number`.
+    fn signed_txn(
+        sender: AccountAddress,
+        sequence_number: u64,
+        gas_unit_price: u64,
+        code_len: usize,
+    ) -> SignedTransaction {
+        let private_key = Ed25519PrivateKey::generate_for_testing();
+        let public_key = private_key.public_key();
+        let raw_txn = RawTransaction::new(
+            sender,
+            sequence_number,
+            TransactionPayload::Script(Script::new(vec![0u8; code_len], vec![], vec![])),
+            0,
+            gas_unit_price,
+            0,
+            ChainId::test(),
+        );
+        raw_txn
+            .sign(&private_key, public_key)
+            .expect("signing should succeed")
+            .into_inner()
+    }
+
+    #[test]
+    fn a_too_large_txn_is_skipped_without_aborting_selection_of_smaller_ones_behind_it() {
+        let expensive_but_oversized = signed_txn(AccountAddress::random(), 0, 100, 1_000);
+        let cheaper_but_small = signed_txn(AccountAddress::random(), 0, 1, 8);
+
+        let oversized_bytes = expensive_but_oversized.raw_txn_bytes_len() as u64;
+        let small_bytes = cheaper_but_small.raw_txn_bytes_len() as u64;
+        // A budget that only the small txn fits in, not the oversized one.
+        let budget = PayloadTxnsSize::new(2, small_bytes + 1);
+        assert!(oversized_bytes > budget.size_in_bytes());
+
+        let selected = MixedPayloadClient::select_by_fee(
+            vec![expensive_but_oversized, cheaper_but_small.clone()],
+            budget,
+        );
+
+        assert_eq!(selected, vec![cheaper_but_small]);
+    }
+
+    #[test]
+    fn count_budget_exhaustion_stops_selection_entirely() {
+        let sender_a = signed_txn(AccountAddress::random(), 0, 10, 8);
+        let sender_b = signed_txn(AccountAddress::random(), 0, 5, 8);
+        let total_bytes = (sender_a.raw_txn_bytes_len() + sender_b.raw_txn_bytes_len()) as u64;
+
+        // Byte budget would allow both, but the count budget only allows one.
+        let budget = PayloadTxnsSize::new(1, total_bytes);
+        let selected = MixedPayloadClient::select_by_fee(vec![sender_a.clone(), sender_b], budget);
+
+        assert_eq!(selected, vec![sender_a]);
+    }
+
+    #[test]
+    fn higher_gas_price_is_preferred_across_senders() {
+        let high_price = signed_txn(AccountAddress::random(), 0, 100, 8);
+        let low_price = signed_txn(AccountAddress::random(), 0, 1, 8);
+        let budget = PayloadTxnsSize::new(1, u64::MAX);
+
+        let selected =
+            MixedPayloadClient::select_by_fee(vec![low_price, high_price.clone()], budget);
+
+        assert_eq!(selected, vec![high_price]);
+    }
+
+    #[test]
+    fn a_senders_txns_are_never_reordered_out_of_sequence_number_order() {
+        let sender = AccountAddress::random();
+        // Earlier sequence number has a lower gas price than the one behind it; fee-based
+        // selection must still pick them in sequence-number order.
+        let txn0 = signed_txn(sender, 0, 1, 8);
+        let txn1 = signed_txn(sender, 1, 100, 8);
+        let budget = PayloadTxnsSize::new(2, u64::MAX);
+
+        let selected = MixedPayloadClient::select_by_fee(vec![txn0.clone(), txn1.clone()], budget);
+
+        assert_eq!(selected, vec![txn0, txn1]);
+    }
+
+    #[test]
+    fn a_gas_price_tie_across_senders_is_broken_the_same_way_every_time() {
+        // Two different senders' head txns tie on gas price; only one fits.
+        let txn_a = signed_txn(AccountAddress::random(), 0, 42, 8);
+        let txn_b = signed_txn(AccountAddress::random(), 0, 42, 8);
+        let budget = PayloadTxnsSize::new(1, u64::MAX);
+
+        let first_run =
+            MixedPayloadClient::select_by_fee(vec![txn_a.clone(), txn_b.clone()], budget);
+        let second_run = MixedPayloadClient::select_by_fee(vec![txn_a, txn_b], budget);
+
+        // Exactly one of the tied candidates is selected, and the tie-break is deterministic
+        // (not e.g. HashMap-iteration-order dependent) across repeated runs with the same input.
+        assert_eq!(first_run.len(), 1);
+        assert_eq!(first_run, second_run);
+    }
+}
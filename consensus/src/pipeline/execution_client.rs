@@ -16,9 +16,7 @@ use crate::{
 use anyhow::{anyhow, Result};
 use aptos_channels::aptos_channel;
 use aptos_consensus_types::{
-    common::Round,
-    pipelined_block::PipelinedBlock,
-    wrapped_ledger_info::WrappedLedgerInfo,
+    common::Round, pipelined_block::PipelinedBlock, wrapped_ledger_info::WrappedLedgerInfo,
 };
 use aptos_crypto::bls12381::PrivateKey;
 use aptos_executor_types::ExecutorResult;
@@ -26,11 +24,76 @@ use aptos_types::{
     epoch_state::EpochState,
     ledger_info::LedgerInfoWithSignatures,
     on_chain_config::{OnChainConsensusConfig, OnChainExecutionConfig, OnChainRandomnessConfig},
+    transaction::Version,
     validator_signer::ValidatorSigner,
 };
 use futures::channel::mpsc::UnboundedSender;
 use move_core_types::account_address::AccountAddress;
-use std::{sync::Arc, time::Duration};
+use std::{
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+use tokio::time::sleep;
+
+/// Periodic progress update emitted while `sync_for_duration`/`sync_to_target` are in flight, so
+/// a node that is far behind gives feedback instead of being an opaque await.
+#[derive(Clone, Debug)]
+pub struct SyncProgress {
+    pub synced_version: Version,
+    pub target_version: Option<Version>,
+    pub committed_round: Round,
+    /// Versions synced per second since the previous progress update.
+    pub rate: f64,
+}
+
+/// Default time without a version advance before a sync is considered stalled.
+pub const DEFAULT_SYNC_STALL_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Polling cadence for `DummyExecutionClient::sync_for_duration`'s watchdog/progress-sink loop.
+const DUMMY_SYNC_POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Detects a stalled sync: no synced-version advance observed within `stall_timeout`. This
+/// mirrors the periodic connection-liveness check pattern: `observe` is called every time new
+/// synced ledger info comes in, and `check` returns an error once the timeout elapses without an
+/// observed advance, so callers can abort and retry against a different peer instead of blocking
+/// indefinitely.
+pub struct SyncStallWatchdog {
+    stall_timeout: Duration,
+    last_progress_at: Instant,
+    last_synced_version: Version,
+}
+
+impl SyncStallWatchdog {
+    pub fn new(stall_timeout: Duration) -> Self {
+        Self {
+            stall_timeout,
+            last_progress_at: Instant::now(),
+            last_synced_version: 0,
+        }
+    }
+
+    /// Resets the watchdog if `synced_version` advanced past the last observed version. Call
+    /// this at the same point synced ledger info is observed, so the watchdog and the progress
+    /// sink stay in lockstep.
+    pub fn observe(&mut self, synced_version: Version) {
+        if synced_version > self.last_synced_version {
+            self.last_synced_version = synced_version;
+            self.last_progress_at = Instant::now();
+        }
+    }
+
+    /// Returns an error if no version advance has been observed within `stall_timeout`.
+    pub fn check(&self) -> Result<(), StateSyncError> {
+        if self.last_progress_at.elapsed() > self.stall_timeout {
+            return Err(StateSyncError::from(anyhow!(
+                "state sync stalled: no progress for {:?} (last synced_version={})",
+                self.stall_timeout,
+                self.last_synced_version,
+            )));
+        }
+        Ok(())
+    }
+}
 
 #[async_trait::async_trait]
 pub trait TExecutionClient: Send + Sync {
@@ -69,12 +132,25 @@ pub trait TExecutionClient: Send + Sync {
     /// Synchronizes for the specified duration and returns the latest synced
     /// ledger info. Note: it is possible that state sync may run longer than
     /// the specified duration (e.g., if the node is very far behind).
+    ///
+    /// An implementation backed by a real sync loop is expected to emit `SyncProgress` updates at
+    /// a fixed cadence and abort via a `SyncStallWatchdog` if no version advance is observed
+    /// within its configured timeout, using `with_sync_progress_reporting` (or the implementation's
+    /// equivalent) to register the sink/timeout. `DummyExecutionClient` has no real sync loop to
+    /// drive those updates from, so it only exercises the watchdog against its own lack of
+    /// progress; see its doc comment.
     async fn sync_for_duration(
         &self,
         duration: Duration,
     ) -> Result<LedgerInfoWithSignatures, StateSyncError>;
 
     /// Synchronize to a commit that is not present locally.
+    ///
+    /// An implementation backed by a real sync loop is expected to emit `SyncProgress` updates at
+    /// a fixed cadence and abort via a `SyncStallWatchdog` if no version advance is observed
+    /// within its configured timeout, using `with_sync_progress_reporting` (or the implementation's
+    /// equivalent) to register the sink/timeout. `DummyExecutionClient` has no real sync loop to
+    /// drive those updates from; see its doc comment.
     async fn sync_to_target(&self, target: LedgerInfoWithSignatures) -> Result<(), StateSyncError>;
 
     /// Resets the internal state of the rand and buffer managers.
@@ -132,7 +208,45 @@ impl BufferManagerHandle {
     }
 }
 
-pub struct DummyExecutionClient;
+/// No-op `TExecutionClient`, used where a real execution pipeline isn't wired up (e.g. tests,
+/// scaffolding). `with_sync_progress_reporting` registers a progress sink/stall timeout, a
+/// builder-set callback rather than a trait-wide parameter, so adding this capability doesn't
+/// require every `TExecutionClient` implementor to change; since this client has no execution
+/// pipeline and therefore no real synced-version history to replay, its `sync_for_duration`/
+/// `sync_to_target` only exercise that sink/watchdog against the fact that nothing ever advances,
+/// rather than simulating a successful sync. See each method's doc comment for specifics.
+pub struct DummyExecutionClient {
+    sync_progress_sink: Mutex<Option<UnboundedSender<SyncProgress>>>,
+    stall_timeout: Mutex<Duration>,
+}
+
+impl DummyExecutionClient {
+    pub fn new() -> Self {
+        Self {
+            sync_progress_sink: Mutex::new(None),
+            stall_timeout: Mutex::new(DEFAULT_SYNC_STALL_TIMEOUT),
+        }
+    }
+
+    /// Registers `progress_sink` to receive a `SyncProgress` update, and `stall_timeout` to
+    /// bound how long the `SyncStallWatchdog` allows a sync to go without a version advance,
+    /// for future syncs driven by this client.
+    pub fn with_sync_progress_reporting(
+        self,
+        progress_sink: UnboundedSender<SyncProgress>,
+        stall_timeout: Duration,
+    ) -> Self {
+        *self.sync_progress_sink.lock().expect("lock not poisoned") = Some(progress_sink);
+        *self.stall_timeout.lock().expect("lock not poisoned") = stall_timeout;
+        self
+    }
+}
+
+impl Default for DummyExecutionClient {
+    fn default() -> Self {
+        Self::new()
+    }
+}
 
 #[async_trait::async_trait]
 impl TExecutionClient for DummyExecutionClient {
@@ -168,16 +282,72 @@ impl TExecutionClient for DummyExecutionClient {
         Ok(())
     }
 
+    /// There's no execution pipeline behind this client, so there's no synced ledger info it
+    /// could honestly hand back. It still drives the watchdog/progress-sink machinery for up to
+    /// `duration`, polling at `DUMMY_SYNC_POLL_INTERVAL`, so a caller that wired up
+    /// `with_sync_progress_reporting` sees the same "no progress" signal it would from a real
+    /// client whose peer stopped responding, rather than an opaque await. Since nothing ever
+    /// advances, this always ends in an error: either the watchdog trips first, or `duration`
+    /// elapses first.
     async fn sync_for_duration(
         &self,
-        _: Duration,
+        duration: Duration,
     ) -> Result<LedgerInfoWithSignatures, StateSyncError> {
-        Err(StateSyncError::from(anyhow!(
-            "sync_for_duration() is not supported by the DummyExecutionClient!"
-        )))
+        let stall_timeout = *self.stall_timeout.lock().expect("lock not poisoned");
+        let mut watchdog = SyncStallWatchdog::new(stall_timeout);
+        let progress_sink = self
+            .sync_progress_sink
+            .lock()
+            .expect("lock not poisoned")
+            .clone();
+        let deadline = Instant::now() + duration;
+
+        loop {
+            watchdog.check()?;
+            if let Some(sink) = progress_sink.as_ref() {
+                let _ = sink.unbounded_send(SyncProgress {
+                    synced_version: 0,
+                    target_version: None,
+                    committed_round: 0,
+                    rate: 0.0,
+                });
+            }
+            let now = Instant::now();
+            if now >= deadline {
+                return Err(StateSyncError::from(anyhow!(
+                    "sync_for_duration() is not supported by the DummyExecutionClient! (no execution pipeline to advance; waited {:?})",
+                    duration
+                )));
+            }
+            sleep(DUMMY_SYNC_POLL_INTERVAL.min(deadline - now)).await;
+        }
     }
 
-    async fn sync_to_target(&self, _: LedgerInfoWithSignatures) -> Result<(), StateSyncError> {
+    /// Unlike `sync_for_duration`, `target` is already known, so there's no intermediate state to
+    /// poll for: this completes in a single step, observing `target`'s version as an immediate
+    /// (zero-elapsed) advance and emitting exactly one `SyncProgress`. That's enough to validate
+    /// a caller's watchdog/sink wiring end-to-end, but it is not a stand-in for the periodic,
+    /// multi-tick cadence a real sync loop would produce while catching up to `target`.
+    async fn sync_to_target(&self, target: LedgerInfoWithSignatures) -> Result<(), StateSyncError> {
+        let target_version = target.ledger_info().version();
+        let mut watchdog =
+            SyncStallWatchdog::new(*self.stall_timeout.lock().expect("lock not poisoned"));
+        watchdog.observe(target_version);
+        watchdog.check()?;
+
+        if let Some(sink) = self
+            .sync_progress_sink
+            .lock()
+            .expect("lock not poisoned")
+            .as_ref()
+        {
+            let _ = sink.unbounded_send(SyncProgress {
+                synced_version: target_version,
+                target_version: Some(target_version),
+                committed_round: 0,
+                rate: 0.0,
+            });
+        }
         Ok(())
     }
 
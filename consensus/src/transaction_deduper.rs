@@ -1,7 +1,13 @@
 // Copyright © Aptos Foundation
 // SPDX-License-Identifier: Apache-2.0
 
+use aptos_crypto::HashValue;
 use aptos_types::transaction::SignedTransaction;
+use move_core_types::account_address::AccountAddress;
+use std::{
+    collections::{HashSet, VecDeque},
+    sync::{Arc, Mutex},
+};
 
 /// Interface to dedup transactions. The dedup filters duplicate transactions within a block.
 pub trait TransactionDeduper: Send + Sync {
@@ -17,3 +23,185 @@ impl TransactionDeduper for NoOpDeduper {
     }
 }
 
+/// Identity of a transaction used to detect duplicates across blocks. Two transactions are
+/// considered the same if they share the same sender, sequence number and content hash.
+type TxnIdentity = (AccountAddress, u64, HashValue);
+
+fn txn_identity(txn: &SignedTransaction) -> TxnIdentity {
+    (txn.sender(), txn.sequence_number(), txn.committed_hash())
+}
+
+/// Bounded FIFO ring of recently-seen txn identities, backed by a `HashSet` for O(1) membership
+/// checks. Once `capacity` is exceeded the oldest identity is evicted.
+struct SeenWindow {
+    capacity: usize,
+    order: VecDeque<TxnIdentity>,
+    seen: HashSet<TxnIdentity>,
+}
+
+impl SeenWindow {
+    fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            order: VecDeque::with_capacity(capacity),
+            seen: HashSet::with_capacity(capacity),
+        }
+    }
+
+    /// Returns true if `identity` was already present, otherwise records it and returns false.
+    fn check_and_insert(&mut self, identity: TxnIdentity) -> bool {
+        if !self.seen.insert(identity) {
+            return true;
+        }
+        self.order.push_back(identity);
+        if self.order.len() > self.capacity {
+            if let Some(evicted) = self.order.pop_front() {
+                self.seen.remove(&evicted);
+            }
+        }
+        false
+    }
+}
+
+/// Deduper that filters out transactions seen in previous calls, not just within the current
+/// block. Keeps a bounded sliding window of recently-seen `(sender, sequence_number, hash)`
+/// identities so duplicate transactions that slipped through multiple quorum-store batches are
+/// dropped before execution, while memory usage stays bounded across many blocks.
+pub struct WindowedDeduper {
+    window: Mutex<SeenWindow>,
+}
+
+impl WindowedDeduper {
+    /// `capacity` is the number of distinct txn identities retained across calls, e.g. the
+    /// expected number of txns in the last N blocks.
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            window: Mutex::new(SeenWindow::new(capacity)),
+        }
+    }
+}
+
+impl TransactionDeduper for WindowedDeduper {
+    fn dedup(&self, txns: Vec<SignedTransaction>) -> Vec<SignedTransaction> {
+        let mut window = self.window.lock().expect("lock not poisoned");
+        txns.into_iter()
+            .filter(|txn| !window.check_and_insert(txn_identity(txn)))
+            .collect()
+    }
+}
+
+/// Selects the `TransactionDeduper` implementation from `TransactionDeduperConfig`:
+/// `NoDedup` gives `NoOpDeduper` (previous behavior), `TxnHashAndAuthor` gives a
+/// `WindowedDeduper` of that capacity.
+pub fn create_transaction_deduper(
+    dedup_config: TransactionDeduperConfig,
+) -> Arc<dyn TransactionDeduper> {
+    match dedup_config {
+        TransactionDeduperConfig::NoDedup => Arc::new(NoOpDeduper {}),
+        TransactionDeduperConfig::TxnHashAndAuthor(window_size) => {
+            Arc::new(WindowedDeduper::new(window_size))
+        }
+    }
+}
+
+/// Configuration for selecting and tuning the `TransactionDeduper`. It is defined in this crate
+/// and is not a field of `NodeConfig`.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum TransactionDeduperConfig {
+    /// No deduplication across blocks (previous behavior).
+    NoDedup,
+    /// Dedup against a sliding window of the last `window_size` seen txn identities.
+    TxnHashAndAuthor(usize),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aptos_crypto::{ed25519::Ed25519PrivateKey, PrivateKey, Uniform};
+    use aptos_types::{
+        chain_id::ChainId,
+        transaction::{RawTransaction, Script, TransactionPayload},
+    };
+
+    fn signed_txn(sender: AccountAddress, sequence_number: u64) -> SignedTransaction {
+        let private_key = Ed25519PrivateKey::generate_for_testing();
+        let public_key = private_key.public_key();
+        let raw_txn = RawTransaction::new(
+            sender,
+            sequence_number,
+            TransactionPayload::Script(Script::new(vec![], vec![], vec![])),
+            0,
+            0,
+            0,
+            ChainId::test(),
+        );
+        raw_txn
+            .sign(&private_key, public_key)
+            .expect("signing should succeed")
+            .into_inner()
+    }
+
+    #[test]
+    fn dedup_drops_a_txn_seen_in_a_previous_call() {
+        let deduper = WindowedDeduper::new(10);
+        let sender = AccountAddress::random();
+        let txn = signed_txn(sender, 0);
+
+        assert_eq!(deduper.dedup(vec![txn.clone()]).len(), 1);
+        // Same identity seen again in a later call (e.g. a later block) is dropped.
+        assert!(deduper.dedup(vec![txn]).is_empty());
+    }
+
+    #[test]
+    fn dedup_preserves_input_order() {
+        let deduper = WindowedDeduper::new(10);
+        let sender = AccountAddress::random();
+        let txn0 = signed_txn(sender, 0);
+        let txn1 = signed_txn(sender, 1);
+        let txn2 = signed_txn(sender, 2);
+
+        let deduped = deduper.dedup(vec![txn0.clone(), txn1.clone(), txn2.clone()]);
+        assert_eq!(deduped, vec![txn0, txn1, txn2]);
+    }
+
+    #[test]
+    fn dedup_drops_only_the_duplicate_within_a_single_call() {
+        let deduper = WindowedDeduper::new(10);
+        let sender = AccountAddress::random();
+        let txn0 = signed_txn(sender, 0);
+        let txn1 = signed_txn(sender, 1);
+
+        let deduped = deduper.dedup(vec![txn0.clone(), txn1.clone(), txn0.clone()]);
+        assert_eq!(deduped, vec![txn0, txn1]);
+    }
+
+    #[test]
+    fn eviction_at_capacity_allows_the_evicted_identity_to_reappear() {
+        let deduper = WindowedDeduper::new(1);
+        let sender = AccountAddress::random();
+        let txn0 = signed_txn(sender, 0);
+        let txn1 = signed_txn(sender, 1);
+
+        // Fills the window of size 1.
+        assert_eq!(deduper.dedup(vec![txn0.clone()]).len(), 1);
+        // Inserting txn1 evicts txn0's identity from the window.
+        assert_eq!(deduper.dedup(vec![txn1]).len(), 1);
+        // txn0 is no longer tracked, so it is treated as new rather than a duplicate.
+        assert_eq!(deduper.dedup(vec![txn0]).len(), 1);
+    }
+
+    #[test]
+    fn seen_window_check_and_insert_reports_duplicates_and_tracks_capacity() {
+        let mut window = SeenWindow::new(2);
+        let id_a = (AccountAddress::ZERO, 0, HashValue::zero());
+        let id_b = (AccountAddress::ZERO, 1, HashValue::zero());
+        let id_c = (AccountAddress::ZERO, 2, HashValue::zero());
+
+        assert!(!window.check_and_insert(id_a));
+        assert!(window.check_and_insert(id_a)); // duplicate
+        assert!(!window.check_and_insert(id_b));
+        // Capacity 2 is now full with [id_a, id_b]; inserting id_c evicts id_a.
+        assert!(!window.check_and_insert(id_c));
+        assert!(!window.check_and_insert(id_a)); // id_a was evicted, so it's treated as new
+    }
+}